@@ -1,11 +1,37 @@
 use std::path::{Path, PathBuf};
 use crate::exclusions::Exclusions;
 use crate::filter::Filter;
+use crate::gitignore::GitignoreTree;
 use std::fs::metadata;
 use tokio::fs::metadata as tokio_metadata;
-use std::collections::VecDeque;
-use async_recursion::async_recursion;
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use async_stream::stream;
+use futures::stream::{self as fstream, Stream, StreamExt};
 
+// directory identity used for symlink cycle detection: (dev, ino) on Unix, canonicalized path elsewhere
+#[cfg(unix)]
+type DirId = (u64, u64);
+#[cfg(not(unix))]
+type DirId = PathBuf;
+
+// number of directory entries classified (stat'd) concurrently, absent an explicit with_concurrency call
+const DEFAULT_CONCURRENCY: usize = 16;
+
+type FailedPaths = Arc<Mutex<Vec<(PathBuf, std::io::Error)>>>;
+
+// traversal configuration, bundled so `walk` doesn't grow one positional parameter per knob
+struct WalkConfig {
+    exclusions: Exclusions,
+    filter: Filter,
+    respect_gitignore: bool,
+    follow_symlinks: bool,
+    concurrency: usize,
+    min_depth: Option<usize>,
+    max_depth: Option<usize>,
+}
 
 struct FileExplorer {
     // list of directories/files to scan
@@ -14,12 +40,29 @@ struct FileExplorer {
     exclusions: Exclusions,
     // filter files types
     filter: Filter,
-    // pending walk directories
+    // whether nested `.gitignore` files prune the walk (default: true)
+    respect_gitignore: bool,
+    // whether directory symlinks are descended into (default: false, matching walkdir)
+    follow_symlinks: bool,
+    // number of directory entries stat'd concurrently
+    concurrency: usize,
+    // shallowest depth a file may be yielded from (base paths are depth 0); unbounded when `None`
+    min_depth: Option<usize>,
+    // deepest depth the walk will descend into; unbounded when `None`
+    max_depth: Option<usize>,
+    // pending walk directories, consumed once the stream starts
     walk_dirs: VecDeque<PathBuf>,
-    // pending walk files
+    // pending walk files, consumed once the stream starts
     walk_files: VecDeque<PathBuf>,
-    // accumulation of all paths which failed scan
-    failed_paths: Vec<(PathBuf, std::io::Error)>,
+    // base paths that failed classification in `new()`, yielded as `Err`
+    // once the stream starts so they're observable like every other failure
+    pending_base_errors: Vec<(PathBuf, std::io::Error)>,
+    // accumulation of all paths which failed scan, shared with the stream
+    // so it stays readable after (or while) the explorer is consumed
+    failed_paths: FailedPaths,
+    // the underlying traversal, built lazily on first poll once all
+    // `with_*` configuration has had a chance to run
+    stream: Option<Pin<Box<dyn Stream<Item = std::io::Result<PathBuf>> + Send>>>,
     //TODO (guyt): base path Option<Path> (19/07/2022)
 }
 
@@ -29,8 +72,10 @@ impl FileExplorer {
     fn new(base_paths: Vec<PathBuf>, exclusions: Exclusions, filter: Filter) -> std::io::Result<Self> {
         let mut dirs = VecDeque::new();
         let mut files = VecDeque::new();
+        let failed_paths: FailedPaths = Arc::new(Mutex::new(vec![]));
+        let mut pending_base_errors = Vec::new();
         for base_path in base_paths.iter() {
-            match Self::is_dir(base_path) {
+            match is_dir(base_path) {
                 Ok(res) => {
                     if res {
                         dirs.push_front(base_path.to_owned())
@@ -39,7 +84,8 @@ impl FileExplorer {
                     }
                 }
                 Err(err) => {
-                    println!("Failed adding path to scan. Path: {:?}. Error: {}", base_path, err);
+                    failed_paths.lock().unwrap().push((base_path.to_owned(), clone_io_error(&err)));
+                    pending_base_errors.push((base_path.to_owned(), err));
                 }
             }
         }
@@ -49,60 +95,278 @@ impl FileExplorer {
             base_paths,
             exclusions,
             filter,
+            respect_gitignore: true,
+            follow_symlinks: false,
+            concurrency: DEFAULT_CONCURRENCY,
+            min_depth: None,
+            max_depth: None,
             walk_dirs: dirs,
             walk_files: files,
-            failed_paths: vec![],
+            pending_base_errors,
+            failed_paths,
+            stream: None,
         })
     }
 
-    fn is_dir(path: &Path) -> std::io::Result<bool> {
-        let md = metadata(path)?;
-        Ok(md.is_dir())
+    // enables/disables .gitignore handling (default: true). no effect once the stream has started
+    fn with_gitignore(mut self, enabled: bool) -> Self {
+        self.respect_gitignore = enabled;
+        self
     }
 
-    async fn async_is_dir(path: &Path) -> std::io::Result<bool> {
-        let md = tokio_metadata(path).await?;
-        Ok(md.is_dir())
+    // enables descending into directory symlinks (default: false, matching walkdir); tracks ancestor
+    // directory identity to skip a symlink that loops back on itself. no effect once the stream has started
+    fn with_follow_symlinks(mut self, enabled: bool) -> Self {
+        self.follow_symlinks = enabled;
+        self
     }
 
-    #[async_recursion]
-    async fn next(&mut self) -> Option<PathBuf> {
-        if let Some(next_file) = self.walk_files.pop_back() {
-            return Some(next_file);
+    // how many directory entries are classified (stat'd) concurrently (default: DEFAULT_CONCURRENCY).
+    // no effect once the stream has started
+    fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    // does not yield files shallower than min_depth (base paths are depth 0, their direct children
+    // depth 1, and so on). unbounded by default. no effect once the stream has started
+    fn with_min_depth(mut self, min_depth: usize) -> Self {
+        self.min_depth = Some(min_depth);
+        self
+    }
+
+    // does not descend past max_depth. unbounded by default. no effect once the stream has started
+    fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    // snapshot of every path that failed to scan so far; the same failures are also yielded as `Err`
+    // items from the stream itself
+    fn failed_paths(&self) -> Vec<(PathBuf, std::io::Error)> {
+        let failed = self.failed_paths.lock().unwrap();
+        failed.iter().map(|(path, err)| (path.clone(), clone_io_error(err))).collect()
+    }
+}
+
+impl Stream for FileExplorer {
+    type Item = std::io::Result<PathBuf>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.stream.is_none() {
+            let walk_dirs = std::mem::take(&mut this.walk_dirs);
+            let walk_files = std::mem::take(&mut this.walk_files);
+            let pending_base_errors = std::mem::take(&mut this.pending_base_errors);
+            let config = WalkConfig {
+                exclusions: this.exclusions.clone(),
+                filter: this.filter.clone(),
+                respect_gitignore: this.respect_gitignore,
+                follow_symlinks: this.follow_symlinks,
+                concurrency: this.concurrency,
+                min_depth: this.min_depth,
+                max_depth: this.max_depth,
+            };
+            this.stream = Some(Box::pin(walk(walk_dirs, walk_files, pending_base_errors, config, this.failed_paths.clone())));
         }
+        this.stream.as_mut().unwrap().as_mut().poll_next(cx)
+    }
+}
 
-        let next_dir = self.walk_dirs.pop_back()?;
-        let mut entries = match tokio::fs::read_dir(&next_dir).await {
-            Ok(entries) => entries,
-            Err(err) => {
-                println!("Failed reading dir with error {} for dir {:?}", err, next_dir);
-                self.failed_paths.push((next_dir, err));
-                return self.next().await;
+fn depth_allowed(depth: usize, min_depth: Option<usize>) -> bool {
+    min_depth.is_none_or(|min| depth >= min)
+}
+
+fn clone_io_error(err: &std::io::Error) -> std::io::Error {
+    std::io::Error::new(err.kind(), err.to_string())
+}
+
+fn is_dir(path: &Path) -> std::io::Result<bool> {
+    let md = metadata(path)?;
+    Ok(md.is_dir())
+}
+
+// classifies an entry without following symlinks: a symlink (whatever it points to) is always a file.
+// the symlink_metadata call this needs describes the link itself, not its target, so it's only handed
+// back for reuse by Filter when the entry turned out not to be a symlink
+async fn classify_no_follow(path: &Path) -> std::io::Result<(bool, Option<std::fs::Metadata>)> {
+    let md = tokio::fs::symlink_metadata(path).await?;
+    if md.file_type().is_symlink() {
+        Ok((false, None))
+    } else {
+        Ok((md.is_dir(), Some(md)))
+    }
+}
+
+// classifies an entry following symlinks
+async fn async_is_dir(path: &Path) -> std::io::Result<(bool, Option<std::fs::Metadata>)> {
+    let md = tokio_metadata(path).await?;
+    Ok((md.is_dir(), Some(md)))
+}
+
+// classifies a directory entry, preferring the file type read_dir already reported over a fresh stat:
+// DirEntry::file_type is free on platforms whose readdir populates d_type, and only falls back to a
+// metadata/symlink_metadata call when it doesn't. that fallback's Metadata, when fetched, is handed
+// back alongside the classification so Filter::matches can reuse it instead of stat-ing again
+async fn classify(
+    entry: tokio::fs::DirEntry,
+    follow_symlinks: bool,
+) -> (PathBuf, std::io::Result<(bool, Option<std::fs::Metadata>)>) {
+    let path = entry.path();
+    let result = match entry.file_type().await {
+        Ok(file_type) if file_type.is_symlink() => {
+            if follow_symlinks {
+                async_is_dir(&path).await
+            } else {
+                Ok((false, None))
             }
-        };
+        }
+        Ok(file_type) => Ok((file_type.is_dir(), None)),
+        Err(_) if follow_symlinks => async_is_dir(&path).await,
+        Err(_) => classify_no_follow(&path).await,
+    };
+    (path, result)
+}
 
-        while let Ok(Some(entry)) = entries.next_entry().await {
-            match Self::async_is_dir(&entry.path()).await {
-                Ok(is_dir) => {
-                    if is_dir {
-                        self.walk_dirs.push_back(entry.path())
-                    } else {
-                        self.walk_files.push_back(entry.path())
-                    }
+#[cfg(unix)]
+async fn dir_id(path: &Path) -> std::io::Result<DirId> {
+    use std::os::unix::fs::MetadataExt;
+    let md = tokio_metadata(path).await?;
+    Ok((md.dev(), md.ino()))
+}
+
+#[cfg(not(unix))]
+async fn dir_id(path: &Path) -> std::io::Result<DirId> {
+    tokio::fs::canonicalize(path).await
+}
+
+// drives the traversal itself, as an async_stream::stream! generator so the recursive, queue-based
+// walk can yield results directly instead of threading state through a hand-rolled Future/Poll impl
+fn walk(
+    mut walk_dirs: VecDeque<PathBuf>,
+    walk_files: VecDeque<PathBuf>,
+    pending_base_errors: Vec<(PathBuf, std::io::Error)>,
+    config: WalkConfig,
+    failed_paths: FailedPaths,
+) -> impl Stream<Item = std::io::Result<PathBuf>> {
+    let WalkConfig { exclusions, filter, respect_gitignore, follow_symlinks, concurrency, min_depth, max_depth } = config;
+    stream! {
+        for (_, err) in pending_base_errors {
+            yield Err(err);
+        }
+
+        let mut gitignore_tree = GitignoreTree::new();
+        let mut dir_ancestors: HashMap<PathBuf, Vec<DirId>> = HashMap::new();
+        let mut dir_depths: HashMap<PathBuf, usize> = walk_dirs.iter().map(|path| (path.clone(), 0)).collect();
+
+        if depth_allowed(0, min_depth) {
+            for file in walk_files {
+                if !exclusions.excludes(&file) && filter.matches(&file, None).await {
+                    yield Ok(file);
                 }
+            }
+        }
+
+        while let Some(next_dir) = walk_dirs.pop_back() {
+            if exclusions.excludes(&next_dir) {
+                continue;
+            }
+            let depth = dir_depths.remove(&next_dir).unwrap_or(0);
+            if max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+            if respect_gitignore {
+                gitignore_tree.load(&next_dir);
+            }
+
+            let ancestor_ids = if follow_symlinks {
+                match dir_ancestors.remove(&next_dir) {
+                    Some(ids) => ids,
+                    None => match dir_id(&next_dir).await {
+                        Ok(id) => vec![id],
+                        Err(err) => {
+                            failed_paths.lock().unwrap().push((next_dir, clone_io_error(&err)));
+                            yield Err(err);
+                            continue;
+                        }
+                    },
+                }
+            } else {
+                Vec::new()
+            };
+
+            let mut read_dir = match tokio::fs::read_dir(&next_dir).await {
+                Ok(read_dir) => read_dir,
                 Err(err) => {
-                    println!("Failed reading entry with error {} for path {:?}", err, entry.path());
-                    self.failed_paths.push((entry.path(), err))
+                    failed_paths.lock().unwrap().push((next_dir, clone_io_error(&err)));
+                    yield Err(err);
+                    continue;
+                }
+            };
+            let mut dir_entries = Vec::new();
+            while let Ok(Some(entry)) = read_dir.next_entry().await {
+                dir_entries.push(entry);
+            }
+
+            let mut classified = fstream::iter(dir_entries)
+                .map(|entry| classify(entry, follow_symlinks))
+                .buffer_unordered(concurrency);
+
+            while let Some((entry_path, classification)) = classified.next().await {
+                match classification {
+                    Ok((is_dir, metadata)) => {
+                        if exclusions.excludes(&entry_path) {
+                            continue;
+                        }
+                        if respect_gitignore && gitignore_tree.is_ignored(&entry_path, is_dir) {
+                            continue;
+                        }
+                        if !is_dir {
+                            if depth_allowed(depth + 1, min_depth) && filter.matches(&entry_path, metadata.as_ref()).await {
+                                yield Ok(entry_path);
+                            }
+                            continue;
+                        }
+                        if max_depth.is_some_and(|max| depth + 1 > max) {
+                            continue;
+                        }
+                        if follow_symlinks {
+                            match dir_id(&entry_path).await {
+                                Ok(id) => {
+                                    if ancestor_ids.contains(&id) {
+                                        let err = std::io::Error::other(format!("symlink cycle detected at {:?}", entry_path));
+                                        failed_paths.lock().unwrap().push((entry_path, clone_io_error(&err)));
+                                        yield Err(err);
+                                        continue;
+                                    }
+                                    let mut child_ancestors = ancestor_ids.clone();
+                                    child_ancestors.push(id);
+                                    dir_ancestors.insert(entry_path.clone(), child_ancestors);
+                                }
+                                Err(err) => {
+                                    failed_paths.lock().unwrap().push((entry_path, clone_io_error(&err)));
+                                    yield Err(err);
+                                    continue;
+                                }
+                            }
+                        }
+                        dir_depths.insert(entry_path.clone(), depth + 1);
+                        walk_dirs.push_back(entry_path);
+                    }
+                    Err(err) => {
+                        failed_paths.lock().unwrap().push((entry_path, clone_io_error(&err)));
+                        yield Err(err);
+                    }
                 }
             }
         }
-        self.next().await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::FileExplorer;
+    use futures::StreamExt;
     use pretty_assertions::{assert_eq};
     use std::path::{Path, PathBuf};
     use rand::distributions::{Alphanumeric, DistString};
@@ -111,15 +375,103 @@ mod tests {
     use crate::exclusions::Exclusions;
     use crate::filter::Filter;
 
+    #[tokio::test]
+    async fn test_max_depth_stops_recursion() {
+        let outer_dir = new_dir();
+        let outer_files = 3;
+        create_files_in_dir(outer_dir.path(), outer_files).await;
+
+        let inner_dir = TempDir::new_in(outer_dir.path(), "inner").unwrap();
+        create_files_in_dir(inner_dir.path(), 5).await;
+
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap().with_max_depth(1);
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        // the inner dir's files (depth 2) are never reached; its own entry (depth 1) is kept.
+        assert_eq!(counter, outer_files);
+    }
+
+    #[tokio::test]
+    async fn test_min_depth_skips_shallow_files() {
+        let outer_dir = new_dir();
+        create_files_in_dir(outer_dir.path(), 3).await;
+
+        let inner_dir = TempDir::new_in(outer_dir.path(), "inner").unwrap();
+        let inner_files = 5;
+        create_files_in_dir(inner_dir.path(), inner_files).await;
+
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap().with_min_depth(2);
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        assert_eq!(counter, inner_files);
+    }
+
+    #[tokio::test]
+    async fn test_exclusions_prune_matching_dir() {
+        let outer_dir = new_dir();
+        create_files_in_dir(outer_dir.path(), 3).await;
+
+        let excluded_dir = TempDir::new_in(outer_dir.path(), "node_modules").unwrap();
+        create_files_in_dir(excluded_dir.path(), 5).await;
+
+        let exclusions = Exclusions::from_strings(vec!["node_modules*"]).unwrap();
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], exclusions, Filter::new()).unwrap();
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        assert_eq!(counter, 3);
+    }
+
+    #[tokio::test]
+    async fn test_filter_keeps_only_matching_extension() {
+        let dir = new_dir();
+        create_files_in_dir(dir.path(), 3).await;
+        tokio::fs::write(dir.path().join("keep.txt"), b"hello").await.unwrap();
+
+        let filter = Filter::new().with_extensions(["txt"]);
+        let mut explorer = FileExplorer::new(vec![dir.path().to_owned()], Exclusions::new(), filter).unwrap();
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        assert_eq!(counter, 1);
+    }
+
+    #[tokio::test]
+    async fn test_filter_skips_empty_files() {
+        let dir = new_dir();
+        create_files_in_dir(dir.path(), 3).await;
+        tokio::fs::write(dir.path().join("nonempty"), b"hello").await.unwrap();
+
+        let filter = Filter::new().skip_empty_files();
+        let mut explorer = FileExplorer::new(vec![dir.path().to_owned()], Exclusions::new(), filter).unwrap();
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        assert_eq!(counter, 1);
+    }
+
     #[tokio::test]
     async fn test_iterate_files_in_dir() {
         let dir = new_dir();
         let number_of_files = 5;
         create_files_in_dir(dir.path(), number_of_files).await;
-        let mut explorer = FileExplorer::new(vec![dir.path().to_owned()], Exclusions {}, Filter {}).unwrap();
+        let mut explorer = FileExplorer::new(vec![dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap();
 
         let mut counter = 0;
-        while let Some(_) = explorer.next().await {
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
             counter += 1;
         }
         assert_eq!(counter, number_of_files);
@@ -133,10 +485,11 @@ mod tests {
         let number_of_files = 5;
         create_files_in_dir(first_dir.path(), number_of_files).await;
         create_files_in_dir(second_dir.path(), number_of_files).await;
-        let mut explorer = FileExplorer::new(vec![first_dir.path().to_owned(), second_dir.path().to_owned()], Exclusions {}, Filter {}).unwrap();
+        let mut explorer = FileExplorer::new(vec![first_dir.path().to_owned(), second_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap();
 
         let mut counter = 0;
-        while let Some(_) = explorer.next().await {
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
             counter += 1;
         }
         assert_eq!(counter, number_of_files * 2);
@@ -152,9 +505,10 @@ mod tests {
         let inner_files = 5;
         create_files_in_dir(inner_dir.path(), inner_files).await;
 
-        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions {}, Filter {}).unwrap();
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap();
         let mut counter = 0;
-        while let Some(_) = explorer.next().await {
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
             counter += 1;
         }
         assert_eq!(counter, outer_files + inner_files);
@@ -170,12 +524,135 @@ mod tests {
         let inner_files = 5;
         create_files_in_dir(inner_dir.path(), inner_files).await;
 
-        let mut explorer = FileExplorer::new(vec!["does not exist".into(), outer_dir.path().to_owned(), "does not exist".into()], Exclusions {}, Filter {}).unwrap();
+        let mut explorer = FileExplorer::new(vec!["does not exist".into(), outer_dir.path().to_owned(), "does not exist".into()], Exclusions::new(), Filter::new()).unwrap();
+        let mut oks = 0;
+        let mut errs = 0;
+        while let Some(item) = explorer.next().await {
+            match item {
+                Ok(_) => oks += 1,
+                Err(_) => errs += 1,
+            }
+        }
+        // the two bad base paths surface as errors, both from the stream and from failed_paths().
+        assert_eq!(oks, outer_files + inner_files);
+        assert_eq!(errs, 2);
+        assert_eq!(explorer.failed_paths().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gitignore_prunes_ignored_dir() {
+        let outer_dir = new_dir();
+        create_files_in_dir(outer_dir.path(), 3).await;
+
+        let ignored_dir = TempDir::new_in(outer_dir.path(), "ignored").unwrap();
+        create_files_in_dir(ignored_dir.path(), 5).await;
+
+        tokio::fs::write(outer_dir.path().join(".gitignore"), format!("{}/\n", ignored_dir.path().file_name().unwrap().to_str().unwrap())).await.unwrap();
+
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap();
         let mut counter = 0;
-        while let Some(_) = explorer.next().await {
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
             counter += 1;
         }
-        assert_eq!(counter, outer_files + inner_files);
+        // 3 files plus the .gitignore itself; the ignored subdir is pruned entirely.
+        assert_eq!(counter, 4);
+    }
+
+    #[tokio::test]
+    async fn test_gitignore_does_not_prune_an_explicit_base_path() {
+        let outer_dir = new_dir();
+        create_files_in_dir(outer_dir.path(), 3).await;
+
+        let ignored_dir = TempDir::new_in(outer_dir.path(), "ignored").unwrap();
+        let ignored_files = 5;
+        create_files_in_dir(ignored_dir.path(), ignored_files).await;
+
+        tokio::fs::write(outer_dir.path().join(".gitignore"), format!("{}/\n", ignored_dir.path().file_name().unwrap().to_str().unwrap())).await.unwrap();
+
+        // passed directly as a base path rather than discovered while walking `outer_dir`
+        let mut explorer = FileExplorer::new(vec![ignored_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap();
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        assert_eq!(counter, ignored_files);
+    }
+
+    #[tokio::test]
+    async fn test_gitignore_disabled_keeps_everything() {
+        let outer_dir = new_dir();
+        create_files_in_dir(outer_dir.path(), 3).await;
+
+        let ignored_dir = TempDir::new_in(outer_dir.path(), "ignored").unwrap();
+        create_files_in_dir(ignored_dir.path(), 5).await;
+
+        tokio::fs::write(outer_dir.path().join(".gitignore"), format!("{}/\n", ignored_dir.path().file_name().unwrap().to_str().unwrap())).await.unwrap();
+
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap().with_gitignore(false);
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        assert_eq!(counter, 3 + 1 + 5);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlinked_dir_not_followed_by_default() {
+        let outer_dir = new_dir();
+        create_files_in_dir(outer_dir.path(), 3).await;
+
+        let target_dir = new_dir();
+        create_files_in_dir(target_dir.path(), 5).await;
+        std::os::unix::fs::symlink(target_dir.path(), outer_dir.path().join("link")).unwrap();
+
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap();
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        // the symlink itself counts as a file; its target's contents are not descended into.
+        assert_eq!(counter, 4);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlink_cycle_detected_when_following() {
+        let outer_dir = new_dir();
+        create_files_in_dir(outer_dir.path(), 3).await;
+        std::os::unix::fs::symlink(outer_dir.path(), outer_dir.path().join("self_link")).unwrap();
+
+        let mut explorer = FileExplorer::new(vec![outer_dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap().with_follow_symlinks(true);
+        let mut oks = 0;
+        let mut errs = 0;
+        while let Some(item) = explorer.next().await {
+            match item {
+                Ok(_) => oks += 1,
+                Err(_) => errs += 1,
+            }
+        }
+        assert_eq!(oks, 3);
+        assert_eq!(errs, 1);
+        assert_eq!(explorer.failed_paths().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_classification_sees_every_file() {
+        let dir = new_dir();
+        let number_of_files = 50;
+        create_files_in_dir(dir.path(), number_of_files).await;
+        let mut explorer = FileExplorer::new(vec![dir.path().to_owned()], Exclusions::new(), Filter::new()).unwrap().with_concurrency(4);
+
+        let mut counter = 0;
+        while let Some(item) = explorer.next().await {
+            item.unwrap();
+            counter += 1;
+        }
+        assert_eq!(counter, number_of_files);
     }
 
     async fn create_files_in_dir(dir: &Path, number_of_files: usize) {
@@ -195,4 +672,4 @@ mod tests {
     fn new_dir() -> TempDir {
         TempDir::new(&*rand_string()).unwrap()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+
+// paths and patterns that should never be scanned (node_modules, .git, target, *.tmp, ...). when an
+// excluded pattern matches a directory, the directory is pruned before its children are ever read
+#[derive(Clone, Default)]
+pub struct Exclusions {
+    absolute_paths: HashSet<String>,
+    globs: Vec<Pattern>,
+}
+
+impl Exclusions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // builds an exclusion set from user-supplied strings, as a CLI would receive them: an absolute
+    // path is matched exactly; everything else (a bare name like node_modules or a pattern like
+    // *.tmp) is matched as a glob against both the entry's name and its full path
+    pub fn from_strings<I, S>(patterns: I) -> Result<Self, glob::PatternError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut exclusions = Self::default();
+        for pattern in patterns {
+            exclusions.add(&pattern.into())?;
+        }
+        Ok(exclusions)
+    }
+
+    // builds an exclusion set from paths, each matched exactly
+    pub fn from_paths<I>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        let absolute_paths = paths.into_iter().map(|path| normalize(&path.to_string_lossy())).collect();
+        Self { absolute_paths, globs: Vec::new() }
+    }
+
+    fn add(&mut self, pattern: &str) -> Result<(), glob::PatternError> {
+        if Path::new(pattern).is_absolute() {
+            self.absolute_paths.insert(normalize(pattern));
+        } else {
+            self.globs.push(Pattern::new(&normalize(pattern))?);
+        }
+        Ok(())
+    }
+
+    // whether path should be pruned from the walk
+    pub(crate) fn excludes(&self, path: &Path) -> bool {
+        let full_path = normalize(&path.to_string_lossy());
+        if self.absolute_paths.contains(&full_path) {
+            return true;
+        }
+        let name = path.file_name().and_then(|name| name.to_str()).map(normalize).unwrap_or_default();
+        self.globs.iter().any(|pattern| pattern.matches(&name) || pattern.matches(&full_path))
+    }
+}
+
+#[cfg(windows)]
+fn normalize(value: &str) -> String {
+    value.to_lowercase()
+}
+
+#[cfg(not(windows))]
+fn normalize(value: &str) -> String {
+    value.to_owned()
+}
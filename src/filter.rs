@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use glob::Pattern;
+
+// predicate applied to every file before it is yielded by FileExplorer. an empty Filter (the
+// default) passes everything through; every predicate configured must pass (AND semantics)
+#[derive(Clone, Default)]
+pub struct Filter {
+    include_extensions: Option<HashSet<String>>,
+    exclude_extensions: HashSet<String>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    globs: Vec<Pattern>,
+    skip_empty: bool,
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // keeps only files whose extension (case-insensitive, without the leading `.`) is one of extensions
+    pub fn with_extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.include_extensions = Some(extensions.into_iter().map(|ext| ext.into().to_lowercase()).collect());
+        self
+    }
+
+    // drops files whose extension (case-insensitive) is one of extensions
+    pub fn without_extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude_extensions = extensions.into_iter().map(|ext| ext.into().to_lowercase()).collect();
+        self
+    }
+
+    // keeps only files at least min_size bytes
+    pub fn with_min_size(mut self, min_size: u64) -> Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    // keeps only files at most max_size bytes
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    // keeps only files whose name or full path matches at least one of patterns
+    pub fn with_globs<I, S>(mut self, patterns: I) -> Result<Self, glob::PatternError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for pattern in patterns {
+            self.globs.push(Pattern::new(pattern.as_ref())?);
+        }
+        Ok(self)
+    }
+
+    // drops zero-byte files, which can never be meaningful duplicates
+    pub fn skip_empty_files(mut self) -> Self {
+        self.skip_empty = true;
+        self
+    }
+
+    // whether path passes every predicate configured on this filter. metadata is whatever the caller
+    // already had on hand from classifying the entry (often None, since the common fast path there
+    // never stats the file); a size predicate or skip_empty_files falls back to its own stat otherwise
+    pub(crate) async fn matches(&self, path: &Path, metadata: Option<&std::fs::Metadata>) -> bool {
+        if let Some(include) = &self.include_extensions {
+            match extension_of(path) {
+                Some(ext) if include.contains(&ext) => {}
+                _ => return false,
+            }
+        }
+
+        if !self.exclude_extensions.is_empty() {
+            if let Some(ext) = extension_of(path) {
+                if self.exclude_extensions.contains(&ext) {
+                    return false;
+                }
+            }
+        }
+
+        if !self.globs.is_empty() {
+            let name = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+            let full_path = path.to_string_lossy();
+            if !self.globs.iter().any(|pattern| pattern.matches(name) || pattern.matches(&full_path)) {
+                return false;
+            }
+        }
+
+        if self.min_size.is_some() || self.max_size.is_some() || self.skip_empty {
+            let size = match metadata {
+                Some(metadata) => metadata.len(),
+                None => match tokio::fs::metadata(path).await {
+                    Ok(metadata) => metadata.len(),
+                    Err(_) => return false,
+                },
+            };
+            if self.skip_empty && size == 0 {
+                return false;
+            }
+            if self.min_size.is_some_and(|min| size < min) {
+                return false;
+            }
+            if self.max_size.is_some_and(|max| size > max) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn extension_of(path: &Path) -> Option<String> {
+    path.extension().and_then(OsStr::to_str).map(|ext| ext.to_lowercase())
+}
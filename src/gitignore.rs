@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+// compiled .gitignore rule sets, keyed by the directory that defined them, so a candidate path can
+// be tested against its nearest enclosing rules (and, failing a match there, against each ancestor)
+#[derive(Default)]
+pub(crate) struct GitignoreTree {
+    layers: HashMap<PathBuf, Gitignore>,
+}
+
+impl GitignoreTree {
+    pub(crate) fn new() -> Self {
+        Self { layers: HashMap::new() }
+    }
+
+    // loads dir/.gitignore, if present, and compiles it for later lookups. a directory with no
+    // .gitignore simply contributes no rules
+    pub(crate) fn load(&mut self, dir: &Path) {
+        let gitignore_path = dir.join(".gitignore");
+        if !gitignore_path.is_file() {
+            return;
+        }
+        let mut builder = GitignoreBuilder::new(dir);
+        if builder.add(&gitignore_path).is_none() {
+            if let Ok(gitignore) = builder.build() {
+                self.layers.insert(dir.to_path_buf(), gitignore);
+            }
+        }
+    }
+
+    // whether path is ignored, honoring standard gitignore precedence: the nearest enclosing
+    // .gitignore is consulted first, and a `!` re-include there wins even over a parent directory's
+    // exclusion. if the nearest rule set has no opinion, each ancestor is checked in turn
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for ancestor in path.ancestors().skip(1) {
+            let Some(gitignore) = self.layers.get(ancestor) else { continue };
+            let matched = gitignore.matched(path, is_dir);
+            if matched.is_ignore() {
+                return true;
+            }
+            if matched.is_whitelist() {
+                return false;
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,4 @@
+pub mod collector;
+pub mod exclusions;
+pub mod filter;
+mod gitignore;